@@ -1,14 +1,26 @@
 use crate::providers::embedder::{Embedder, GenericEmbedder};
 use crate::providers::llm::{GenericLLM, LLM};
-use crate::providers::provider::{Provider, ProviderID};
+use crate::providers::provider::{Provider, ProviderConfig, ProviderID};
 use anyhow::Result;
 use async_trait::async_trait;
 
-pub struct AI21Provider {}
+pub struct AI21Provider {
+    config: ProviderConfig,
+}
 
 impl AI21Provider {
     pub fn new() -> Self {
-        AI21Provider {}
+        Self::new_with_config(ProviderConfig::default())
+    }
+
+    pub fn new_with_config(config: ProviderConfig) -> Self {
+        AI21Provider { config }
+    }
+}
+
+impl Default for AI21Provider {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -19,6 +31,7 @@ impl Provider for AI21Provider {
     }
 
     fn setup(&self) -> Result<()> {
+        self.config.build_client()?;
         Ok(())
     }
 
@@ -1,14 +1,26 @@
 use crate::providers::embedder::{Embedder, GenericEmbedder};
 use crate::providers::llm::{GenericLLM, LLM};
-use crate::providers::provider::{Provider, ProviderID};
+use crate::providers::provider::{Provider, ProviderConfig, ProviderID};
 use anyhow::Result;
 use async_trait::async_trait;
 
-pub struct AnthropicProvider {}
+pub struct AnthropicProvider {
+    config: ProviderConfig,
+}
 
 impl AnthropicProvider {
     pub fn new() -> Self {
-        AnthropicProvider {}
+        Self::new_with_config(ProviderConfig::default())
+    }
+
+    pub fn new_with_config(config: ProviderConfig) -> Self {
+        AnthropicProvider { config }
+    }
+}
+
+impl Default for AnthropicProvider {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -19,6 +31,7 @@ impl Provider for AnthropicProvider {
     }
 
     fn setup(&self) -> Result<()> {
+        self.config.build_client()?;
         Ok(())
     }
 
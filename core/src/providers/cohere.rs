@@ -1,14 +1,26 @@
 use crate::providers::embedder::{Embedder, GenericEmbedder};
 use crate::providers::llm::{GenericLLM, LLM};
-use crate::providers::provider::{Provider, ProviderID};
+use crate::providers::provider::{Provider, ProviderConfig, ProviderID};
 use anyhow::Result;
 use async_trait::async_trait;
 
-pub struct CohereProvider {}
+pub struct CohereProvider {
+    config: ProviderConfig,
+}
 
 impl CohereProvider {
     pub fn new() -> Self {
-        CohereProvider {}
+        Self::new_with_config(ProviderConfig::default())
+    }
+
+    pub fn new_with_config(config: ProviderConfig) -> Self {
+        CohereProvider { config }
+    }
+}
+
+impl Default for CohereProvider {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -19,6 +31,7 @@ impl Provider for CohereProvider {
     }
 
     fn setup(&self) -> Result<()> {
+        self.config.build_client()?;
         Ok(())
     }
 
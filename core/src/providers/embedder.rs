@@ -1,13 +1,29 @@
+use crate::providers::embedder_models::EmbedderModel;
+use anyhow::Result;
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait Embedder {
     fn id(&self) -> String;
+
+    // Validates the embedder's configuration, including that `id()` is a
+    // model this provider has metadata for. Called before first use so an
+    // unknown model id fails fast instead of at first request.
+    fn setup(&self) -> Result<()>;
+
+    // Both fail (rather than fall back to `0`) when `id()` isn't one of
+    // `supported_models()`, so an unrecognized model can't be mistaken for
+    // a legitimately-empty embedder.
+    fn max_input_tokens(&self) -> Result<usize>;
+    fn dimensions(&self) -> Result<usize>;
+
+    // Models this embedder carries metadata for. Empty for embedders (like
+    // `GenericEmbedder`) that don't track per-model metadata.
+    fn supported_models(&self) -> &'static [EmbedderModel] {
+        &[]
+    }
 }
 
-// Minimal `Embedder` used by providers that don't carry model metadata
-// (embeddings request/response wiring for these lives elsewhere in the
-// crate).
 pub struct GenericEmbedder {
     id: String,
 }
@@ -23,4 +39,16 @@ impl Embedder for GenericEmbedder {
     fn id(&self) -> String {
         self.id.clone()
     }
+
+    fn setup(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn max_input_tokens(&self) -> Result<usize> {
+        Ok(0)
+    }
+
+    fn dimensions(&self) -> Result<usize> {
+        Ok(0)
+    }
 }
@@ -0,0 +1,68 @@
+use anyhow::{anyhow, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbedderModel {
+    pub id: &'static str,
+    max_input_tokens: usize,
+    dimensions: usize,
+}
+
+impl EmbedderModel {
+    pub fn max_input_tokens(&self) -> usize {
+        self.max_input_tokens
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+}
+
+pub const OPENAI_EMBEDDER_MODELS: &[EmbedderModel] = &[
+    EmbedderModel {
+        id: "text-embedding-ada-002",
+        max_input_tokens: 8191,
+        dimensions: 1536,
+    },
+    EmbedderModel {
+        id: "text-embedding-3-small",
+        max_input_tokens: 8191,
+        dimensions: 1536,
+    },
+    EmbedderModel {
+        id: "text-embedding-3-large",
+        max_input_tokens: 8191,
+        dimensions: 3072,
+    },
+];
+
+pub fn from_name(models: &'static [EmbedderModel], name: &str) -> Result<EmbedderModel> {
+    models.iter().find(|m| m.id == name).copied().ok_or_else(|| {
+        anyhow!(
+            "Unknown embedding model `{}` (possible values: {})",
+            name,
+            models
+                .iter()
+                .map(|m| m.id)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_name_finds_known_model() {
+        let m = from_name(OPENAI_EMBEDDER_MODELS, "text-embedding-ada-002").unwrap();
+        assert_eq!(m.max_input_tokens(), 8191);
+        assert_eq!(m.dimensions(), 1536);
+    }
+
+    #[test]
+    fn from_name_errors_on_unknown_model_with_valid_names_listed() {
+        let err = from_name(OPENAI_EMBEDDER_MODELS, "not-a-real-model").unwrap_err();
+        assert!(err.to_string().contains("text-embedding-ada-002"));
+    }
+}
@@ -1,14 +1,26 @@
 use crate::providers::embedder::{Embedder, GenericEmbedder};
 use crate::providers::llm::{GenericLLM, LLM};
-use crate::providers::provider::{Provider, ProviderID};
+use crate::providers::provider::{Provider, ProviderConfig, ProviderID};
 use anyhow::Result;
 use async_trait::async_trait;
 
-pub struct GoogleAiStudioProvider {}
+pub struct GoogleAiStudioProvider {
+    config: ProviderConfig,
+}
 
 impl GoogleAiStudioProvider {
     pub fn new() -> Self {
-        GoogleAiStudioProvider {}
+        Self::new_with_config(ProviderConfig::default())
+    }
+
+    pub fn new_with_config(config: ProviderConfig) -> Self {
+        GoogleAiStudioProvider { config }
+    }
+}
+
+impl Default for GoogleAiStudioProvider {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -19,6 +31,7 @@ impl Provider for GoogleAiStudioProvider {
     }
 
     fn setup(&self) -> Result<()> {
+        self.config.build_client()?;
         Ok(())
     }
 
@@ -1,14 +1,26 @@
 use crate::providers::embedder::{Embedder, GenericEmbedder};
 use crate::providers::llm::{GenericLLM, LLM};
-use crate::providers::provider::{Provider, ProviderID};
+use crate::providers::provider::{Provider, ProviderConfig, ProviderID};
 use anyhow::Result;
 use async_trait::async_trait;
 
-pub struct MistralProvider {}
+pub struct MistralProvider {
+    config: ProviderConfig,
+}
 
 impl MistralProvider {
     pub fn new() -> Self {
-        MistralProvider {}
+        Self::new_with_config(ProviderConfig::default())
+    }
+
+    pub fn new_with_config(config: ProviderConfig) -> Self {
+        MistralProvider { config }
+    }
+}
+
+impl Default for MistralProvider {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -19,6 +31,7 @@ impl Provider for MistralProvider {
     }
 
     fn setup(&self) -> Result<()> {
+        self.config.build_client()?;
         Ok(())
     }
 
@@ -3,8 +3,10 @@ pub mod anthropic;
 pub mod azure_openai;
 pub mod cohere;
 pub mod embedder;
+pub mod embedder_models;
 pub mod google_ai_studio;
 pub mod llm;
 pub mod mistral;
 pub mod openai;
+pub mod openai_compatible;
 pub mod provider;
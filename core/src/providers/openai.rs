@@ -1,6 +1,7 @@
 use crate::providers::embedder::Embedder;
+use crate::providers::embedder_models::{self, EmbedderModel, OPENAI_EMBEDDER_MODELS};
 use crate::providers::llm::LLM;
-use crate::providers::provider::{Provider, ProviderID};
+use crate::providers::provider::{Provider, ProviderConfig, ProviderID};
 use anyhow::Result;
 use async_trait::async_trait;
 
@@ -10,6 +11,7 @@ pub struct OpenAILLM {
     id: String,
     base_url: String,
     api_key: Option<String>,
+    config: ProviderConfig,
 }
 
 impl OpenAILLM {
@@ -18,16 +20,41 @@ impl OpenAILLM {
             id,
             OPENAI_BASE_URL.to_string(),
             std::env::var("OPENAI_API_KEY").ok(),
+            ProviderConfig::default(),
         )
     }
 
-    pub fn new_with_endpoint(id: String, base_url: String, api_key: Option<String>) -> Self {
+    pub fn new_with_endpoint(
+        id: String,
+        base_url: String,
+        api_key: Option<String>,
+        config: ProviderConfig,
+    ) -> Self {
         OpenAILLM {
             id,
             base_url,
             api_key,
+            config,
         }
     }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
+    pub fn client(&self) -> Result<reqwest::Client> {
+        self.config.build_client()
+    }
+}
+
+impl Default for OpenAILLM {
+    fn default() -> Self {
+        Self::new(String::new())
+    }
 }
 
 #[async_trait]
@@ -41,6 +68,7 @@ pub struct OpenAIEmbedder {
     id: String,
     base_url: String,
     api_key: Option<String>,
+    config: ProviderConfig,
 }
 
 impl OpenAIEmbedder {
@@ -49,16 +77,41 @@ impl OpenAIEmbedder {
             id,
             OPENAI_BASE_URL.to_string(),
             std::env::var("OPENAI_API_KEY").ok(),
+            ProviderConfig::default(),
         )
     }
 
-    pub fn new_with_endpoint(id: String, base_url: String, api_key: Option<String>) -> Self {
+    pub fn new_with_endpoint(
+        id: String,
+        base_url: String,
+        api_key: Option<String>,
+        config: ProviderConfig,
+    ) -> Self {
         OpenAIEmbedder {
             id,
             base_url,
             api_key,
+            config,
         }
     }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
+    pub fn client(&self) -> Result<reqwest::Client> {
+        self.config.build_client()
+    }
+}
+
+impl Default for OpenAIEmbedder {
+    fn default() -> Self {
+        Self::new(String::new())
+    }
 }
 
 #[async_trait]
@@ -66,13 +119,42 @@ impl Embedder for OpenAIEmbedder {
     fn id(&self) -> String {
         self.id.clone()
     }
+
+    fn setup(&self) -> Result<()> {
+        embedder_models::from_name(OPENAI_EMBEDDER_MODELS, &self.id)?;
+        Ok(())
+    }
+
+    fn max_input_tokens(&self) -> Result<usize> {
+        Ok(embedder_models::from_name(OPENAI_EMBEDDER_MODELS, &self.id)?.max_input_tokens())
+    }
+
+    fn dimensions(&self) -> Result<usize> {
+        Ok(embedder_models::from_name(OPENAI_EMBEDDER_MODELS, &self.id)?.dimensions())
+    }
+
+    fn supported_models(&self) -> &'static [EmbedderModel] {
+        OPENAI_EMBEDDER_MODELS
+    }
 }
 
-pub struct OpenAIProvider {}
+pub struct OpenAIProvider {
+    config: ProviderConfig,
+}
 
 impl OpenAIProvider {
     pub fn new() -> Self {
-        OpenAIProvider {}
+        Self::new_with_config(ProviderConfig::default())
+    }
+
+    pub fn new_with_config(config: ProviderConfig) -> Self {
+        OpenAIProvider { config }
+    }
+}
+
+impl Default for OpenAIProvider {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -83,6 +165,7 @@ impl Provider for OpenAIProvider {
     }
 
     fn setup(&self) -> Result<()> {
+        self.config.build_client()?;
         Ok(())
     }
 
@@ -95,6 +178,7 @@ impl Provider for OpenAIProvider {
             id,
             OPENAI_BASE_URL.to_string(),
             std::env::var("OPENAI_API_KEY").ok(),
+            self.config.clone(),
         ))
     }
 
@@ -103,6 +187,38 @@ impl Provider for OpenAIProvider {
             id,
             OPENAI_BASE_URL.to_string(),
             std::env::var("OPENAI_API_KEY").ok(),
+            self.config.clone(),
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn setup_succeeds_for_known_model() {
+        let embedder = OpenAIEmbedder::new("text-embedding-ada-002".to_string());
+        assert!(embedder.setup().is_ok());
+    }
+
+    #[test]
+    fn setup_errors_for_unknown_model() {
+        let embedder = OpenAIEmbedder::new("not-a-real-model".to_string());
+        assert!(embedder.setup().is_err());
+    }
+
+    #[test]
+    fn dimensions_and_max_input_tokens_match_model_metadata() {
+        let embedder = OpenAIEmbedder::new("text-embedding-ada-002".to_string());
+        assert_eq!(embedder.dimensions().unwrap(), 1536);
+        assert_eq!(embedder.max_input_tokens().unwrap(), 8191);
+    }
+
+    #[test]
+    fn dimensions_errors_for_unknown_model() {
+        let embedder = OpenAIEmbedder::new("not-a-real-model".to_string());
+        assert!(embedder.dimensions().is_err());
+        assert!(embedder.max_input_tokens().is_err());
+    }
+}
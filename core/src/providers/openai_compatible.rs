@@ -0,0 +1,80 @@
+use crate::providers::embedder::Embedder;
+use crate::providers::llm::LLM;
+use crate::providers::openai::{OpenAIEmbedder, OpenAILLM};
+use crate::providers::provider::{Provider, ProviderConfig, ProviderID};
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+const OPENAI_COMPATIBLE_BASE_URL_DEFAULT: &str = "https://api.openai.com/v1";
+
+pub struct OpenAICompatibleProvider {
+    base_url: String,
+    api_key: Option<String>,
+    config: ProviderConfig,
+}
+
+impl OpenAICompatibleProvider {
+    pub fn new() -> Self {
+        Self::new_with_config(ProviderConfig::default())
+    }
+
+    pub fn new_with_config(config: ProviderConfig) -> Self {
+        OpenAICompatibleProvider {
+            base_url: std::env::var("OPENAI_COMPATIBLE_BASE_URL")
+                .unwrap_or_else(|_| OPENAI_COMPATIBLE_BASE_URL_DEFAULT.to_string()),
+            api_key: std::env::var("OPENAI_COMPATIBLE_API_KEY").ok(),
+            config,
+        }
+    }
+}
+
+impl Default for OpenAICompatibleProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Provider for OpenAICompatibleProvider {
+    fn id(&self) -> ProviderID {
+        ProviderID::OpenAICompatible
+    }
+
+    fn setup(&self) -> Result<()> {
+        info!(
+            base_url = self.base_url.as_str(),
+            "Setting up OpenAI-compatible provider",
+        );
+
+        Ok(())
+    }
+
+    async fn test(&self) -> Result<()> {
+        if self.api_key.is_none() {
+            warn!(
+                "`OPENAI_COMPATIBLE_API_KEY` is not set, requests will be sent without authentication",
+            );
+        }
+
+        Ok(())
+    }
+
+    fn llm(&self, id: String) -> Box<dyn LLM + Sync + Send> {
+        Box::new(OpenAILLM::new_with_endpoint(
+            id,
+            self.base_url.clone(),
+            self.api_key.clone(),
+            self.config.clone(),
+        ))
+    }
+
+    fn embedder(&self, id: String) -> Box<dyn Embedder + Sync + Send> {
+        Box::new(OpenAIEmbedder::new_with_endpoint(
+            id,
+            self.base_url.clone(),
+            self.api_key.clone(),
+            self.config.clone(),
+        ))
+    }
+}
@@ -6,64 +6,105 @@ use crate::providers::embedder::Embedder;
 use crate::providers::llm::LLM;
 use crate::providers::mistral::MistralProvider;
 use crate::providers::openai::OpenAIProvider;
+use crate::providers::openai_compatible::OpenAICompatibleProvider;
 use crate::utils::ParseError;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use clap::ValueEnum;
 use futures::prelude::*;
+use rand::Rng;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
-use std::time::Duration;
-use tracing::error;
+use std::time::{Duration, Instant};
+use tracing::{error, warn};
 
 use super::google_ai_studio::GoogleAiStudioProvider;
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, ValueEnum, Deserialize)]
-#[serde(rename_all = "lowercase")]
-#[clap(rename_all = "lowercase")]
-pub enum ProviderID {
-    OpenAI,
-    Cohere,
-    AI21,
-    #[serde(rename = "azure_openai")]
-    AzureOpenAI,
-    Anthropic,
-    Mistral,
-    #[serde(rename = "google_ai_studio")]
-    GoogleAiStudio,
-}
+// Collapses the variant<->wire-name<->constructor mapping that `Display`,
+// `FromStr`, `ValueEnum` and the `provider_with_config()` factory each used
+// to repeat by hand (and drift out of sync with one another, e.g. the
+// `FromStr` error message used to omit `anthropic`/`google_ai_studio`).
+// Adding a provider is now one line in the `register_providers!` call below
+// instead of four separate edits.
+macro_rules! register_providers {
+    ($( ($variant:ident, $name:literal, $ctor:expr) ),+ $(,)?) => {
+        #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+        pub enum ProviderID {
+            $(
+                #[serde(rename = $name)]
+                $variant,
+            )+
+        }
+
+        const PROVIDER_ID_NAMES: &[&str] = &[ $( $name ),+ ];
 
-impl fmt::Display for ProviderID {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ProviderID::OpenAI => write!(f, "openai"),
-            ProviderID::Cohere => write!(f, "cohere"),
-            ProviderID::AI21 => write!(f, "ai21"),
-            ProviderID::AzureOpenAI => write!(f, "azure_openai"),
-            ProviderID::Anthropic => write!(f, "anthropic"),
-            ProviderID::Mistral => write!(f, "mistral"),
-            ProviderID::GoogleAiStudio => write!(f, "google_ai_studio"),
+        impl fmt::Display for ProviderID {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $( ProviderID::$variant => write!(f, $name), )+
+                }
+            }
         }
-    }
-}
 
-impl FromStr for ProviderID {
-    type Err = ParseError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "openai" => Ok(ProviderID::OpenAI),
-            "cohere" => Ok(ProviderID::Cohere),
-            "ai21" => Ok(ProviderID::AI21),
-            "azure_openai" => Ok(ProviderID::AzureOpenAI),
-            "anthropic" => Ok(ProviderID::Anthropic),
-            "mistral" => Ok(ProviderID::Mistral),
-            "google_ai_studio" => Ok(ProviderID::GoogleAiStudio),
-            _ => Err(ParseError::with_message(
-                "Unknown provider ID (possible values: openai, cohere, ai21, azure_openai, mistral)",
-            ))?,
+        impl FromStr for ProviderID {
+            type Err = ParseError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $( $name => Ok(ProviderID::$variant), )+
+                    _ => Err(ParseError::with_message(&format!(
+                        "Unknown provider ID (possible values: {})",
+                        PROVIDER_ID_NAMES.join(", "),
+                    )))?,
+                }
+            }
         }
-    }
+
+        impl ValueEnum for ProviderID {
+            fn value_variants<'a>() -> &'a [Self] {
+                &[ $( ProviderID::$variant ),+ ]
+            }
+
+            fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+                match self {
+                    $( ProviderID::$variant => Some(clap::builder::PossibleValue::new($name)), )+
+                }
+            }
+        }
+
+        pub fn provider_with_config(
+            t: ProviderID,
+            config: ProviderConfig,
+        ) -> Box<dyn Provider + Sync + Send> {
+            match t {
+                $( ProviderID::$variant => Box::new($ctor(config)), )+
+            }
+        }
+    };
+}
+
+register_providers!(
+    (OpenAI, "openai", OpenAIProvider::new_with_config),
+    (Cohere, "cohere", CohereProvider::new_with_config),
+    (AI21, "ai21", AI21Provider::new_with_config),
+    (AzureOpenAI, "azure_openai", AzureOpenAIProvider::new_with_config),
+    (Anthropic, "anthropic", AnthropicProvider::new_with_config),
+    (Mistral, "mistral", MistralProvider::new_with_config),
+    (GoogleAiStudio, "google_ai_studio", GoogleAiStudioProvider::new_with_config),
+    (OpenAICompatible, "openai_compatible", OpenAICompatibleProvider::new_with_config),
+);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum RetryJitter {
+    // No jitter, delays are fully deterministic (useful for tests).
+    #[default]
+    None,
+    // Uniform random value in `[0, delay]`.
+    Full,
+    // `delay/2 + rand(0, delay/2)`.
+    Equal,
 }
 
 #[derive(Debug, Clone)]
@@ -71,6 +112,48 @@ pub struct ModelErrorRetryOptions {
     pub sleep: Duration,
     pub factor: u32,
     pub retries: usize,
+    // Upper bound on the computed delay, before jitter and before honoring
+    // `Retry-After` (which is allowed to exceed it, see `next_delay`).
+    pub max_sleep: Duration,
+    pub jitter: RetryJitter,
+}
+
+impl Default for ModelErrorRetryOptions {
+    fn default() -> Self {
+        ModelErrorRetryOptions {
+            sleep: Duration::from_secs(1),
+            factor: 2,
+            retries: 3,
+            max_sleep: Duration::from_secs(1),
+            jitter: RetryJitter::default(),
+        }
+    }
+}
+
+impl ModelErrorRetryOptions {
+    // Computes the next sleep duration given the previous one (if any) and an
+    // optional server-provided `Retry-After` hint, then applies jitter.
+    fn next_delay(&self, prev: Option<Duration>, retry_after: Option<Duration>) -> Duration {
+        let base = match prev {
+            None => std::cmp::min(self.max_sleep, self.sleep),
+            Some(p) => std::cmp::min(self.max_sleep, p * self.factor),
+        };
+        // A `Retry-After` hint is a floor, not a cap: if the provider asks us
+        // to wait longer than `max_sleep` we still honor it so we don't
+        // hammer a rate-limited endpoint.
+        let delay = match retry_after {
+            Some(retry_after) => std::cmp::max(retry_after, base),
+            None => base,
+        };
+        match self.jitter {
+            RetryJitter::None => delay,
+            RetryJitter::Full => rand::thread_rng().gen_range(Duration::ZERO..=delay),
+            RetryJitter::Equal => {
+                let half = delay / 2;
+                half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -78,6 +161,8 @@ pub struct ModelError {
     pub message: String,
     pub retryable: Option<ModelErrorRetryOptions>,
     pub request_id: Option<String>,
+    // Parsed from the provider's `Retry-After` response header, when present.
+    pub retry_after: Option<Duration>,
 }
 
 impl std::fmt::Display for ModelError {
@@ -93,26 +178,75 @@ impl std::fmt::Display for ModelError {
 
 impl std::error::Error for ModelError {}
 
+// Default threshold above which a single attempt is logged as slow when
+// using the plain (non-`*_with_stats`) variant below.
+const DEFAULT_SLOW_ATTEMPT_THRESHOLD: Duration = Duration::from_secs(30);
+
+// Latency/retry telemetry for a single `with_retryable_back_off_with_stats`
+// call, so callers can surface "took N retries over M seconds" to users and
+// dashboards instead of only learning about retries on terminal failure.
+#[derive(Debug, Clone)]
+pub struct AttemptStats {
+    pub attempts: usize,
+    pub total_elapsed: Duration,
+    pub retried_errors: Vec<String>,
+}
+
 pub async fn with_retryable_back_off<F, O>(
-    mut f: impl FnMut() -> F,
+    f: impl FnMut() -> F,
     log_retry: impl Fn(&str, &Duration, usize) -> (),
 ) -> Result<O>
+where
+    F: Future<Output = Result<O, anyhow::Error>>,
+{
+    with_retryable_back_off_with_stats(f, log_retry, DEFAULT_SLOW_ATTEMPT_THRESHOLD, "")
+        .await
+        .map(|(out, _)| out)
+}
+
+// Same as `with_retryable_back_off` but also measures wall-clock time per
+// attempt and in total, emits a `tracing` warning when a single attempt
+// exceeds `slow_threshold`, and returns `AttemptStats` alongside the output
+// so transient slowness that eventually succeeds is observable and not just
+// logged (via `error!`) on terminal failure. `request_context` (e.g.
+// `"openai:gpt-4"`) is attached to the slow-attempt warning so it's
+// actionable without a stack trace.
+pub async fn with_retryable_back_off_with_stats<F, O>(
+    mut f: impl FnMut() -> F,
+    log_retry: impl Fn(&str, &Duration, usize) -> (),
+    slow_threshold: Duration,
+    request_context: &str,
+) -> Result<(O, AttemptStats)>
 where
     F: Future<Output = Result<O, anyhow::Error>>,
 {
     let mut attempts = 0_usize;
     let mut sleep: Option<Duration> = None;
+    let mut retried_errors: Vec<String> = Vec::new();
+    let run_start = Instant::now();
+
     let out = loop {
-        match f().await {
+        let attempt_start = Instant::now();
+        let result = f().await;
+        let attempt_elapsed = attempt_start.elapsed();
+        if attempt_elapsed > slow_threshold {
+            warn!(
+                request_context,
+                attempt = attempts + 1,
+                elapsed_ms = attempt_elapsed.as_millis() as u64,
+                threshold_ms = slow_threshold.as_millis() as u64,
+                "Slow model request attempt",
+            );
+        }
+
+        match result {
             Err(e) => match e.downcast::<ModelError>() {
                 Ok(err) => {
                     match err.retryable.clone() {
                         Some(retry) => {
                             attempts += 1;
-                            sleep = match sleep {
-                                None => Some(retry.sleep),
-                                Some(b) => Some(b * retry.factor),
-                            };
+                            retried_errors.push(err.message.clone());
+                            sleep = Some(retry.next_delay(sleep, err.retry_after));
                             log_retry(&err.message, sleep.as_ref().unwrap(), attempts);
                             tokio::time::sleep(sleep.unwrap()).await;
                             if attempts > retry.retries {
@@ -140,7 +274,58 @@ where
             Ok(out) => break Ok(out),
         }
     };
-    out
+
+    out.map(|out| {
+        (
+            out,
+            AttemptStats {
+                attempts,
+                total_elapsed: run_start.elapsed(),
+                retried_errors,
+            },
+        )
+    })
+}
+
+// Per-provider HTTP transport configuration. This is the single knob users
+// have to tune network behavior (proxying through corporate egress, talking
+// to an Azure gateway, tightening timeouts, injecting auth headers) instead
+// of each provider hardcoding its own `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ProviderConfig {
+    // http/https/socks5 proxy URL, passed straight to `reqwest::Proxy::all`.
+    pub proxy: Option<String>,
+    pub connect_timeout: Option<Duration>,
+    pub request_timeout: Option<Duration>,
+    pub extra_headers: HashMap<String, String>,
+}
+
+impl ProviderConfig {
+    // Builds a `reqwest::Client` honoring this configuration. Providers
+    // should call this instead of `reqwest::Client::new()` when constructing
+    // their HTTP client.
+    pub fn build_client(&self) -> Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(request_timeout) = self.request_timeout {
+            builder = builder.timeout(request_timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        if !self.extra_headers.is_empty() {
+            let mut headers = HeaderMap::new();
+            for (k, v) in &self.extra_headers {
+                headers.insert(HeaderName::from_bytes(k.as_bytes())?, HeaderValue::from_str(v)?);
+            }
+            builder = builder.default_headers(headers);
+        }
+
+        Ok(builder.build()?)
+    }
 }
 
 #[async_trait]
@@ -155,13 +340,119 @@ pub trait Provider {
 }
 
 pub fn provider(t: ProviderID) -> Box<dyn Provider + Sync + Send> {
-    match t {
-        ProviderID::OpenAI => Box::new(OpenAIProvider::new()),
-        ProviderID::Cohere => Box::new(CohereProvider::new()),
-        ProviderID::AI21 => Box::new(AI21Provider::new()),
-        ProviderID::AzureOpenAI => Box::new(AzureOpenAIProvider::new()),
-        ProviderID::Anthropic => Box::new(AnthropicProvider::new()),
-        ProviderID::Mistral => Box::new(MistralProvider::new()),
-        ProviderID::GoogleAiStudio => Box::new(GoogleAiStudioProvider::new()),
+    provider_with_config(t, ProviderConfig::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts(jitter: RetryJitter) -> ModelErrorRetryOptions {
+        ModelErrorRetryOptions {
+            sleep: Duration::from_millis(100),
+            factor: 2,
+            retries: 5,
+            max_sleep: Duration::from_secs(1),
+            jitter,
+        }
+    }
+
+    #[test]
+    fn next_delay_first_attempt_is_clamped_to_max_sleep() {
+        let o = ModelErrorRetryOptions {
+            sleep: Duration::from_secs(10),
+            max_sleep: Duration::from_secs(1),
+            ..opts(RetryJitter::None)
+        };
+        assert_eq!(o.next_delay(None, None), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn next_delay_grows_by_factor_and_clamps() {
+        let o = opts(RetryJitter::None);
+        assert_eq!(o.next_delay(None, None), Duration::from_millis(100));
+        assert_eq!(
+            o.next_delay(Some(Duration::from_millis(100)), None),
+            Duration::from_millis(200)
+        );
+        assert_eq!(
+            o.next_delay(Some(Duration::from_millis(900)), None),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn next_delay_honors_retry_after_above_max_sleep() {
+        let o = opts(RetryJitter::None);
+        assert_eq!(
+            o.next_delay(None, Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn next_delay_retry_after_does_not_shrink_base() {
+        let o = opts(RetryJitter::None);
+        assert_eq!(
+            o.next_delay(Some(Duration::from_millis(500)), Some(Duration::from_millis(10))),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn next_delay_full_jitter_is_bounded_by_delay() {
+        let o = opts(RetryJitter::Full);
+        for _ in 0..100 {
+            let d = o.next_delay(None, None);
+            assert!(d <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn next_delay_equal_jitter_is_at_least_half_delay() {
+        let o = opts(RetryJitter::Equal);
+        for _ in 0..100 {
+            let d = o.next_delay(None, None);
+            assert!(d >= Duration::from_millis(50));
+            assert!(d <= Duration::from_millis(100));
+        }
+    }
+
+    #[tokio::test]
+    async fn with_stats_counts_attempts_and_collects_retried_errors() {
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let result = with_retryable_back_off_with_stats(
+            || {
+                let n = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async move {
+                    if n < 2 {
+                        Err(anyhow::Error::new(ModelError {
+                            message: format!("transient error {}", n),
+                            retryable: Some(ModelErrorRetryOptions {
+                                sleep: Duration::from_millis(1),
+                                factor: 2,
+                                retries: 5,
+                                max_sleep: Duration::from_millis(10),
+                                jitter: RetryJitter::None,
+                            }),
+                            request_id: None,
+                            retry_after: None,
+                        }))
+                    } else {
+                        Ok(42)
+                    }
+                }
+            },
+            |_, _, _| (),
+            Duration::from_secs(30),
+            "test:model",
+        )
+        .await
+        .unwrap();
+
+        let (out, stats) = result;
+        assert_eq!(out, 42);
+        assert_eq!(stats.attempts, 2);
+        assert_eq!(stats.retried_errors.len(), 2);
     }
 }